@@ -1,19 +1,24 @@
 use anyhow::Result;
 use crossterm::{
+    cursor::MoveTo,
     event::{self, Event, KeyCode, KeyEventKind},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::{
     prelude::*,
-    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
 };
 use regex::Regex;
 use std::env;
 use std::io;
 use std::process::{Command, Stdio};
+use std::sync::OnceLock;
 use std::time::{Duration, Instant};
 
+mod profile;
+use profile::ProfileStore;
+
 #[derive(Clone, Copy, PartialEq, Debug)]
 enum Language {
     System,
@@ -26,29 +31,113 @@ struct Flag {
     long: Option<String>,
     desc: String,
     selected: bool,
+    // Принимает значение (--color=WHEN, -o FILE, --jobs[=N])?
+    takes_value: bool,
+    // Форма `[=VALUE]` — валиден и без значения.
+    optional_value: bool,
+    metavar: Option<String>,
+    value: Option<String>,
 }
 
 impl Flag {
-    fn to_display_string(&self) -> String {
-        let checkmark = if self.selected { "[x]" } else { "[ ]" };
-
-        let flags_str = match (&self.short, &self.long) {
+    // Часть строки списка с именами флага (без чекбокса и описания) — также
+    // используется `draw_hyperlink_overlay`, чтобы знать, какой участок строки обернуть в OSC 8.
+    fn flags_label(&self) -> String {
+        let mut flags_str = match (&self.short, &self.long) {
             (Some(s), Some(l)) => format!("{}, {}", s, l),
             (Some(s), None) => format!("{}", s),
             (None, Some(l)) => format!("    {}", l),
             (None, None) => "???".to_string(),
         };
 
-        format!("{} {:<25} | {}", checkmark, flags_str, self.desc)
+        if self.takes_value {
+            match (&self.value, &self.metavar) {
+                (Some(v), _) => flags_str.push_str(&format!("={}", v)),
+                (None, Some(mv)) => flags_str.push_str(&format!("={}", mv)),
+                (None, None) => {}
+            }
+        }
+
+        flags_str
     }
 
-    fn as_arg(&self) -> String {
+    fn to_display_string(&self) -> String {
+        let checkmark = if self.selected { "[x]" } else { "[ ]" };
+        format!("{} {:<25} | {}", checkmark, self.flags_label(), self.desc)
+    }
+
+    // Ключ флага без значения — для сопоставления между перезапросами справки.
+    fn match_key(&self) -> String {
         if let Some(l) = &self.long {
             l.clone()
         } else {
             self.short.clone().unwrap_or_default()
         }
     }
+
+    fn as_arg(&self) -> String {
+        let base = self.match_key();
+        if self.takes_value {
+            if let Some(v) = &self.value {
+                return format!("{}={}", base, v);
+            }
+        }
+        base
+    }
+
+    // Длинные опции со значением передаются одной строкой, короткие — двумя токенами.
+    fn as_args(&self) -> Vec<String> {
+        if self.takes_value {
+            match &self.value {
+                Some(v) => {
+                    if let Some(l) = &self.long {
+                        return vec![format!("{}={}", l, v)];
+                    }
+                    if let Some(s) = &self.short {
+                        return vec![s.clone(), v.clone()];
+                    }
+                }
+                None => {
+                    if !self.optional_value {
+                        return Vec::new();
+                    }
+                }
+            }
+        }
+
+        vec![self.as_arg()]
+    }
+}
+
+// Подкоманда, обнаруженная в секции "Commands:"/"SUBCOMMANDS" текста справки.
+#[derive(Clone, Debug)]
+struct SubCommand {
+    name: String,
+    desc: String,
+}
+
+impl SubCommand {
+    fn to_display_string(&self) -> String {
+        format!("-> {:<25} | {}", self.name, self.desc)
+    }
+}
+
+// Один узел дерева команд: полный путь (напр. ["git", "remote", "add"]),
+// его флаги и дочерние подкоманды, обнаруженные в справке этого узла.
+#[derive(Clone, Debug)]
+struct CommandNode {
+    path: Vec<String>,
+    flags: Vec<Flag>,
+    subcommands: Vec<SubCommand>,
+    // Сырой текст справки, из которого разобраны флаги/подкоманды — хранится
+    // здесь же, чтобы панель деталей не порождала дочерний процесс заново.
+    raw_text: String,
+}
+
+impl CommandNode {
+    fn command_string(&self) -> String {
+        self.path.join(" ")
+    }
 }
 
 enum ExitAction {
@@ -57,37 +146,284 @@ enum ExitAction {
     Cancel,
 }
 
+#[derive(PartialEq)]
+enum View {
+    Picker,
+    Command,
+}
+
+// Режим ввода: обычная навигация, попап, собирающий значение для
+// флага, отмеченного в списке флагом `takes_value`, ввод запроса фильтра
+// или ввод имени профиля для сохранения/загрузки.
+enum Mode {
+    Normal,
+    Input { flag_idx: usize, buffer: String },
+    Filter,
+    SaveProfile { buffer: String },
+    LoadProfile { buffer: String },
+}
+
 struct App {
-    target_cmd: String,
-    flags: Vec<Flag>,
+    view: View,
+    // Закэширован при старте — возврат в пикер (`P`) не пересканирует $PATH.
+    picker_commands: Vec<String>,
+    picker_state: ListState,
+    picker_query: String,
+    picker_filtered: Vec<usize>,
+    picker_error: Option<String>,
+    // Стек узлов дерева команд: последний элемент — текущий уровень навигации,
+    // Backspace/Left просто снимает верхний узел. Пуст, пока не выбрана команда в пикере.
+    nodes: Vec<CommandNode>,
     list_state: ListState,
     should_quit: bool,
     exit_action: ExitAction,
     current_lang: Language,
+    mode: Mode,
+    // Нечёткий фильтр по списку флагов текущего узла (активируется `/`).
+    filter_active: bool,
+    filter_query: String,
+    // Индексы `flags` текущего узла, прошедшие фильтр, отсортированные по
+    // убыванию релевантности. Когда фильтр неактивен, не используется.
+    filtered_indices: Vec<usize>,
+    // Персистентные профили флагов и имя профиля, подставленного в панель заголовка.
+    profiles: ProfileStore,
+    active_profile: String,
+    // Правая панель с подсвеченным сырым текстом справки, переключаемая клавишей `d`.
+    detail_pane: bool,
+    // Кликабельные имя команды в предпросмотре и имена флагов в списке (OSC 8). Включается эвристикой
+    // определения терминала при старте, принудительно переключается клавишей `H`.
+    hyperlinks_enabled: bool,
 }
 
 impl App {
-    fn new(target_cmd: String, flags: Vec<Flag>) -> Self {
+    fn new(mut root: CommandNode) -> Self {
+        let profiles = ProfileStore::load();
+        if let Some(saved) = profiles.get(&root.command_string(), profile::DEFAULT_PROFILE) {
+            apply_profile_args(&mut root.flags, saved);
+        }
+
         let mut list_state = ListState::default();
-        if !flags.is_empty() {
+        if !root.flags.is_empty() || !root.subcommands.is_empty() {
             list_state.select(Some(0));
         }
 
+        let filtered_indices = (0..root.flags.len()).collect();
+
         Self {
-            target_cmd,
-            flags,
+            view: View::Command,
+            picker_commands: scan_path_commands(),
+            picker_state: ListState::default(),
+            picker_query: String::new(),
+            picker_filtered: Vec::new(),
+            picker_error: None,
+            nodes: vec![root],
             list_state,
             should_quit: false,
             exit_action: ExitAction::Cancel,
             current_lang: Language::System,
+            mode: Mode::Normal,
+            filter_active: false,
+            filter_query: String::new(),
+            filtered_indices,
+            profiles,
+            active_profile: profile::DEFAULT_PROFILE.to_string(),
+            detail_pane: false,
+            hyperlinks_enabled: detect_hyperlink_support(),
+        }
+    }
+
+    // Запуск без аргумента: вместо справки сразу показываем пикер команд из $PATH.
+    fn new_picker() -> Self {
+        let picker_commands = scan_path_commands();
+        let mut picker_state = ListState::default();
+        if !picker_commands.is_empty() {
+            picker_state.select(Some(0));
+        }
+        let picker_filtered = (0..picker_commands.len()).collect();
+
+        Self {
+            view: View::Picker,
+            picker_commands,
+            picker_state,
+            picker_query: String::new(),
+            picker_filtered,
+            picker_error: None,
+            nodes: Vec::new(),
+            list_state: ListState::default(),
+            should_quit: false,
+            exit_action: ExitAction::Cancel,
+            current_lang: Language::System,
+            mode: Mode::Normal,
+            filter_active: false,
+            filter_query: String::new(),
+            filtered_indices: Vec::new(),
+            profiles: ProfileStore::load(),
+            active_profile: profile::DEFAULT_PROFILE.to_string(),
+            detail_pane: false,
+            hyperlinks_enabled: detect_hyperlink_support(),
+        }
+    }
+
+    fn picker_recompute_filter(&mut self) {
+        let query = self.picker_query.clone();
+        let mut scored: Vec<(usize, i32)> = self.picker_commands.iter().enumerate()
+            .filter_map(|(i, name)| fuzzy_score(&query, name).map(|score| (i, score)))
+            .collect();
+
+        if !query.is_empty() {
+            scored.sort_by(|a, b| b.1.cmp(&a.1));
+        }
+
+        self.picker_filtered = scored.into_iter().map(|(i, _)| i).collect();
+
+        match self.picker_state.selected() {
+            Some(i) if i >= self.picker_filtered.len() => {
+                self.picker_state.select(if self.picker_filtered.is_empty() { None } else { Some(0) });
+            }
+            None if !self.picker_filtered.is_empty() => self.picker_state.select(Some(0)),
+            _ => {}
+        }
+    }
+
+    fn picker_next(&mut self) {
+        let len = self.picker_filtered.len();
+        if len == 0 { return; }
+        let i = match self.picker_state.selected() {
+            Some(i) => if i >= len - 1 { 0 } else { i + 1 },
+            None => 0,
+        };
+        self.picker_state.select(Some(i));
+    }
+
+    fn picker_previous(&mut self) {
+        let len = self.picker_filtered.len();
+        if len == 0 { return; }
+        let i = match self.picker_state.selected() {
+            Some(i) => if i == 0 { len - 1 } else { i - 1 },
+            None => 0,
+        };
+        self.picker_state.select(Some(i));
+    }
+
+    // При ошибке получения справки остаётся в пикере.
+    fn enter_picker_selection(&mut self) {
+        let display_i = match self.picker_state.selected() {
+            Some(i) => i,
+            None => return,
+        };
+        let cmd_idx = match self.picker_filtered.get(display_i) {
+            Some(&i) => i,
+            None => return,
+        };
+        let name = self.picker_commands[cmd_idx].clone();
+
+        match fetch_node(&[name], self.current_lang) {
+            Ok(mut node) => {
+                if let Some(saved) = self.profiles.get(&node.command_string(), profile::DEFAULT_PROFILE) {
+                    apply_profile_args(&mut node.flags, saved);
+                }
+                self.filtered_indices = (0..node.flags.len()).collect();
+                self.nodes = vec![node];
+                self.list_state = ListState::default();
+                if !self.current().flags.is_empty() || !self.current().subcommands.is_empty() {
+                    self.list_state.select(Some(0));
+                }
+                self.view = View::Command;
+                self.picker_error = None;
+                self.active_profile = profile::DEFAULT_PROFILE.to_string();
+            }
+            Err(e) => {
+                self.picker_error = Some(e.to_string());
+            }
+        }
+    }
+
+    fn back_to_picker(&mut self) {
+        self.nodes.clear();
+        self.view = View::Picker;
+        self.picker_query.clear();
+        self.picker_filtered = (0..self.picker_commands.len()).collect();
+        self.picker_error = None;
+        // Иначе после возврата в пикер и выбора новой команды в заголовке
+        // остаётся старый "/<query>_" от уже неактуального фильтра флагов.
+        self.filter_active = false;
+        self.filter_query.clear();
+        if !self.picker_filtered.is_empty() {
+            self.picker_state.select(Some(0));
+        }
+    }
+
+    fn current(&self) -> &CommandNode {
+        self.nodes.last().expect("стек узлов не может быть пустым")
+    }
+
+    fn current_mut(&mut self) -> &mut CommandNode {
+        self.nodes.last_mut().expect("стек узлов не может быть пустым")
+    }
+
+    fn visible_flag_count(&self) -> usize {
+        if self.filter_active {
+            self.filtered_indices.len()
+        } else {
+            self.current().flags.len()
+        }
+    }
+
+    // Индекс строки в списке -> реальный индекс в `flags` (с учётом фильтра).
+    fn flag_index_at(&self, display_idx: usize) -> Option<usize> {
+        if self.filter_active {
+            self.filtered_indices.get(display_idx).copied()
+        } else if display_idx < self.current().flags.len() {
+            Some(display_idx)
+        } else {
+            None
         }
     }
 
+    fn entry_count(&self) -> usize {
+        self.visible_flag_count() + self.current().subcommands.len()
+    }
+
+    fn recompute_filter(&mut self) {
+        let query = self.filter_query.clone();
+        let mut scored: Vec<(usize, i32)> = self.current().flags.iter().enumerate()
+            .filter_map(|(i, f)| {
+                let candidate = format!(
+                    "{} {} {}",
+                    f.short.as_deref().unwrap_or(""),
+                    f.long.as_deref().unwrap_or(""),
+                    f.desc
+                );
+                fuzzy_score(&query, &candidate).map(|score| (i, score))
+            })
+            .collect();
+
+        if !query.is_empty() {
+            scored.sort_by(|a, b| b.1.cmp(&a.1));
+        }
+
+        self.filtered_indices = scored.into_iter().map(|(i, _)| i).collect();
+
+        if let Some(i) = self.list_state.selected() {
+            if i >= self.entry_count() {
+                self.list_state.select(if self.entry_count() > 0 { Some(0) } else { None });
+            }
+        }
+    }
+
+    // Старые индексы принадлежат прежнему набору флагов, при смене узла/языка сбрасываем.
+    fn reset_filter(&mut self) {
+        self.filter_active = false;
+        self.filter_query.clear();
+        self.filtered_indices = (0..self.current().flags.len()).collect();
+    }
+
     fn next(&mut self) {
-        if self.flags.is_empty() { return; }
+        let len = self.entry_count();
+        if len == 0 { return; }
         let i = match self.list_state.selected() {
             Some(i) => {
-                if i >= self.flags.len() - 1 { 0 } else { i + 1 }
+                if i >= len - 1 { 0 } else { i + 1 }
             }
             None => 0,
         };
@@ -95,22 +431,127 @@ impl App {
     }
 
     fn previous(&mut self) {
-        if self.flags.is_empty() { return; }
+        let len = self.entry_count();
+        if len == 0 { return; }
         let i = match self.list_state.selected() {
             Some(i) => {
-                if i == 0 { self.flags.len() - 1 } else { i - 1 }
+                if i == 0 { len - 1 } else { i - 1 }
             }
             None => 0,
         };
         self.list_state.select(Some(i));
     }
 
-    fn toggle_selection(&mut self) {
-        if let Some(i) = self.list_state.selected() {
-            if i < self.flags.len() {
-                self.flags[i].selected = !self.flags[i].selected;
+    // Флаг со значением, ещё не выбранный: возвращает его индекс, чтобы
+    // вызывающая сторона открыла попап ввода через `begin_input`.
+    fn toggle_selection(&mut self) -> Option<usize> {
+        let display_i = self.list_state.selected()?;
+        if display_i >= self.visible_flag_count() {
+            return None;
+        }
+        let flag_idx = self.flag_index_at(display_i)?;
+
+        let flag = &mut self.current_mut().flags[flag_idx];
+        if flag.takes_value && !flag.selected {
+            return Some(flag_idx);
+        }
+
+        flag.selected = !flag.selected;
+        if !flag.selected {
+            flag.value = None;
+        }
+        None
+    }
+
+    // Открывает попап ввода значения для флага с данным индексом, подставляя
+    // текущее значение флага, если оно есть. Снятие выбора очищает значение,
+    // а отмена попапа (`Esc`) введённое не сохраняет.
+    fn begin_input(&mut self, flag_idx: usize) {
+        let buffer = self.current().flags.get(flag_idx)
+            .and_then(|f| f.value.clone())
+            .unwrap_or_default();
+        self.mode = Mode::Input { flag_idx, buffer };
+    }
+
+    // Подтверждает значение из попапа: если оно пустое и обязательно,
+    // флаг остаётся невыбранным; иначе сохраняет значение и выбирает флаг.
+    fn commit_input(&mut self, flag_idx: usize, buffer: String) {
+        if let Some(flag) = self.current_mut().flags.get_mut(flag_idx) {
+            if buffer.is_empty() && !flag.optional_value {
+                flag.selected = false;
+                flag.value = None;
+            } else {
+                flag.value = if buffer.is_empty() { None } else { Some(buffer) };
+                flag.selected = true;
             }
         }
+        self.mode = Mode::Normal;
+    }
+
+    fn cancel_input(&mut self) {
+        self.mode = Mode::Normal;
+    }
+
+    // Флаг под текущим курсором (для подсветки в панели деталей).
+    fn focused_flag(&self) -> Option<&Flag> {
+        let display_i = self.list_state.selected()?;
+        if display_i >= self.visible_flag_count() {
+            return None;
+        }
+        let flag_idx = self.flag_index_at(display_i)?;
+        self.current().flags.get(flag_idx)
+    }
+
+    // Подкоманда под текущим курсором, если он стоит не на флаге.
+    fn selected_subcommand(&self) -> Option<&SubCommand> {
+        let i = self.list_state.selected()?;
+        let flag_count = self.visible_flag_count();
+        if i < flag_count {
+            return None;
+        }
+        self.current().subcommands.get(i - flag_count)
+    }
+
+    // Заходит в подкоманду под курсором, добавляя новый узел на стек.
+    // Возвращает false, если курсор не на подкоманде или справка не получена.
+    fn enter_subcommand(&mut self) -> bool {
+        let sub = match self.selected_subcommand() {
+            Some(sub) => sub.clone(),
+            None => return false,
+        };
+
+        let mut child_path = self.current().path.clone();
+        child_path.push(sub.name);
+
+        match fetch_node(&child_path, self.current_lang) {
+            Ok(mut node) => {
+                self.active_profile = if let Some(saved) = self.profiles.get(&node.command_string(), profile::DEFAULT_PROFILE) {
+                    apply_profile_args(&mut node.flags, saved);
+                    profile::DEFAULT_PROFILE.to_string()
+                } else {
+                    String::new()
+                };
+                self.nodes.push(node);
+                self.reset_filter();
+                self.list_state.select(if self.entry_count() > 0 { Some(0) } else { None });
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    // Возвращается к родительскому узлу. false, если мы уже в корне.
+    fn pop_node(&mut self) -> bool {
+        if self.nodes.len() <= 1 {
+            return false;
+        }
+        self.nodes.pop();
+        // Заголовок относился к узлу, из которого мы только что вышли — без
+        // перезагрузки справки неизвестно, какой профиль сейчас активен.
+        self.active_profile = String::new();
+        self.reset_filter();
+        self.list_state.select(if self.entry_count() > 0 { Some(0) } else { None });
+        true
     }
 
     fn toggle_language(&mut self) {
@@ -119,19 +560,27 @@ impl App {
             Language::English => Language::System,
         };
 
-        let selected_args: Vec<String> = self.get_selected_args();
+        // match_key(), не as_arg() — иначе флаги со значением не найдут себя
+        // среди заново разобранных, у которых значение ещё не подставлено.
+        let selected: Vec<(String, Option<String>)> = self.current().flags.iter()
+            .filter(|f| f.selected)
+            .map(|f| (f.match_key(), f.value.clone()))
+            .collect();
+        let path = self.current().path.clone();
 
-        match fetch_flags(&self.target_cmd, new_lang) {
-            Ok(mut new_flags) => {
-                for flag in &mut new_flags {
-                    if selected_args.contains(&flag.as_arg()) {
+        match fetch_node(&path, new_lang) {
+            Ok(mut new_node) => {
+                for flag in &mut new_node.flags {
+                    if let Some((_, value)) = selected.iter().find(|(key, _)| *key == flag.match_key()) {
                         flag.selected = true;
+                        flag.value = value.clone();
                     }
                 }
-                self.flags = new_flags;
+                *self.current_mut() = new_node;
                 self.current_lang = new_lang;
+                self.reset_filter();
                 if let Some(i) = self.list_state.selected() {
-                    if i >= self.flags.len() {
+                    if i >= self.entry_count() {
                         self.list_state.select(Some(0));
                     }
                 }
@@ -141,20 +590,56 @@ impl App {
     }
 
     fn get_selected_args(&self) -> Vec<String> {
-        self.flags.iter()
+        self.current().flags.iter()
             .filter(|f| f.selected)
-            .map(|f| f.as_arg())
+            .flat_map(|f| f.as_args())
             .collect()
     }
 
     fn build_preview_string(&self) -> String {
         let args = self.get_selected_args();
+        let path = self.current().command_string();
         if args.is_empty() {
-            self.target_cmd.clone()
+            path
         } else {
-            format!("{} {}", self.target_cmd, args.join(" "))
+            format!("{} {}", path, args.join(" "))
         }
     }
+
+    // В отличие от аргументов запуска, короткие флаги тут не разбиваются на
+    // два токена — так сопоставление по `match_key` остаётся однозначным.
+    fn profile_args(&self) -> Vec<String> {
+        self.current().flags.iter()
+            .filter(|f| f.selected)
+            .map(|f| f.as_arg())
+            .collect()
+    }
+
+    fn save_profile(&mut self, name: &str) {
+        if name.is_empty() {
+            return;
+        }
+        let command = self.current().command_string();
+        let args = self.profile_args();
+        self.profiles.set(&command, name, args);
+        let _ = self.profiles.save();
+        self.active_profile = name.to_string();
+    }
+
+    fn load_profile(&mut self, name: &str) {
+        let command = self.current().command_string();
+        let saved = match self.profiles.get(&command, name) {
+            Some(args) => args.clone(),
+            None => return,
+        };
+
+        for flag in &mut self.current_mut().flags {
+            flag.selected = false;
+            flag.value = None;
+        }
+        apply_profile_args(&mut self.current_mut().flags, &saved);
+        self.active_profile = name.to_string();
+    }
 }
 
 // Запуск с таймаутом
@@ -195,11 +680,12 @@ fn run_with_timeout(mut cmd: Command, timeout: Duration) -> Result<String> {
     }
 }
 
-fn fetch_raw_help(cmd_name: &str, lang: Language) -> Result<String> {
+fn fetch_raw_help(cmd_path: &[String], lang: Language) -> Result<String> {
     // 1. Пробуем --help с жестким таймаутом (1 секунда).
     // Если программа не успела выплюнуть справку за 1с, скорее всего это `sl` или `vim`
 
-    let mut help_cmd = Command::new(cmd_name);
+    let mut help_cmd = Command::new(&cmd_path[0]);
+    help_cmd.args(&cmd_path[1..]);
     help_cmd.arg("--help");
     help_cmd.env("COLUMNS", "500");
     if lang == Language::English { help_cmd.env("LC_ALL", "C"); }
@@ -213,9 +699,12 @@ fn fetch_raw_help(cmd_name: &str, lang: Language) -> Result<String> {
     }
 
     // 2. Fallback: MAN
-    // Если --help отвалился по таймауту или ошибке, идем в man
+    // Если --help отвалился по таймауту или ошибке, идем в man.
+    // Для подкоманд пробуем конвенцию вида `git-remote-add`, как это принято
+    // в man-страницах git/systemctl и подобных инструментов.
+    let man_page = cmd_path.join("-");
     let mut man_cmd = Command::new("man");
-    man_cmd.arg(cmd_name);
+    man_cmd.arg(&man_page);
     man_cmd.env("PAGER", "cat");
     man_cmd.env("MANROFFOPT", "-c");
     man_cmd.env("GROFF_NO_SGR", "1");
@@ -232,14 +721,155 @@ fn fetch_raw_help(cmd_name: &str, lang: Language) -> Result<String> {
     Err(anyhow::anyhow!("Не удалось получить справку."))
 }
 
-fn fetch_flags(cmd_name: &str, lang: Language) -> Result<Vec<Flag>> {
-    let text = fetch_raw_help(cmd_name, lang)?;
+// Сопоставляет сохранённые строки вида "--jobs=4"/"-o" с флагами по match_key.
+fn apply_profile_args(flags: &mut [Flag], saved: &[String]) {
+    for raw in saved {
+        let (key, value) = profile::parse_arg(raw);
+        if let Some(flag) = flags.iter_mut().find(|f| f.match_key() == key) {
+            flag.selected = true;
+            flag.value = value;
+        }
+    }
+}
+
+// Точного способа спросить терминал "умеешь OSC 8?" нет, поэтому отсеиваем
+// известные проблемные случаи. Переопределяется клавишей `H`.
+fn detect_hyperlink_support() -> bool {
+    if let Ok(term_program) = env::var("TERM_PROGRAM") {
+        let lower = term_program.to_lowercase();
+        if lower.contains("vscode") {
+            return false;
+        }
+    }
+
+    if env::var("TERM").map(|t| t == "dumb").unwrap_or(false) {
+        return false;
+    }
+
+    true
+}
+
+// Для подкоманд единого соглашения об адресах нет, ссылка всегда на корневую команду.
+fn doc_url(path: &[String]) -> String {
+    format!("https://man7.org/linux/man-pages/man1/{}.1.html", path[0])
+}
+
+fn osc8_hyperlink(text: &str, url: &str) -> String {
+    format!("\x1b]8;;{}\x1b\\{}\x1b]8;;\x1b\\", url, text)
+}
+
+fn highlight_token_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"--[a-zA-Z0-9][a-zA-Z0-9\-_]*|-[a-zA-Z0-9?]|<[^>]+>|\b[A-Z][A-Z0-9_]+\b").unwrap()
+    })
+}
+
+// Проверяет, встречается ли `tok` в строке как отдельный токен опции/метавара
+// (через `highlight_token_regex`), а не просто как подстрока — иначе "-o"
+// совпал бы и с "--login-options".
+fn line_has_token(line: &str, tok: &str) -> bool {
+    highlight_token_regex().find_iter(line).any(|m| m.as_str() == tok)
+}
+
+fn is_section_heading(trimmed: &str) -> bool {
+    !trimmed.is_empty()
+        && trimmed.chars().any(|c| c.is_alphabetic())
+        && trimmed.chars().all(|c| c.is_uppercase() || c.is_whitespace() || c == ':')
+}
+
+// Заголовки секций целиком жёлтым; опции и метавары — отдельными цветами;
+// токены из `focus` получают жирное подчёркивание.
+fn highlight_help_line(line: &str, focus: &[&str]) -> Line<'static> {
+    if is_section_heading(line.trim()) {
+        return Line::from(Span::styled(
+            line.to_string(),
+            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+        ));
+    }
+
+    let mut spans = Vec::new();
+    let mut last = 0;
+
+    for m in highlight_token_regex().find_iter(line) {
+        if m.start() > last {
+            spans.push(Span::raw(line[last..m.start()].to_string()));
+        }
+
+        let tok = m.as_str();
+        let mut style = if tok.starts_with('-') {
+            Style::default().fg(Color::Cyan)
+        } else {
+            Style::default().fg(Color::Magenta)
+        };
+        if focus.contains(&tok) {
+            style = style.add_modifier(Modifier::BOLD).add_modifier(Modifier::UNDERLINED);
+        }
+        spans.push(Span::styled(tok.to_string(), style));
+
+        last = m.end();
+    }
+
+    if last < line.len() {
+        spans.push(Span::raw(line[last..].to_string()));
+    }
+
+    Line::from(spans)
+}
+
+// None, если query — не подпоследовательность candidate. Бонусы за совпадения
+// подряд и за совпадения на границе слова, чтобы префиксы всплывали выше.
+fn fuzzy_score(query: &str, candidate: &str) -> Option<i32> {
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let candidate: Vec<char> = candidate.to_lowercase().chars().collect();
+    let mut cand_idx = 0usize;
+    let mut prev_match: Option<usize> = None;
+    let mut score = 0i32;
+
+    for qc in query.to_lowercase().chars() {
+        let mut found = None;
+        while cand_idx < candidate.len() {
+            if candidate[cand_idx] == qc {
+                found = Some(cand_idx);
+                break;
+            }
+            cand_idx += 1;
+        }
+
+        let idx = found?;
+        score += 1;
+
+        if prev_match == Some(idx.wrapping_sub(1)) {
+            score += 5;
+        }
+        if idx == 0 || matches!(candidate[idx - 1], ' ' | '-') {
+            score += 3;
+        }
+
+        prev_match = Some(idx);
+        cand_idx = idx + 1;
+    }
+
+    Some(score)
+}
 
-    let re = Regex::new(r"(?m)^\s+(?:(?P<short>-[a-zA-Z0-9?])(?:,?\s+(?P<long>--[a-zA-Z0-9\-_]+))?|(?P<long_only>--[a-zA-Z0-9\-_]+))\s+(?P<desc>.+)$").unwrap();
+fn parse_flags(text: &str) -> Vec<Flag> {
+    // Группа `value` ловит метавар сразу после флага: `=METAVAR`, `[=VALUE]`,
+    // `<...>` или голый ЗАГЛАВНЫЙ токен перед описанием (`-o FILE`).
+    let re = Regex::new(concat!(
+        r"(?m)^\s+(?:(?P<short>-[a-zA-Z0-9?])(?:,?\s+(?P<long>--[a-zA-Z0-9\-_]+))?|(?P<long_only>--[a-zA-Z0-9\-_]+))",
+        // Один пробел перед ЗАГЛАВНЫМ токеном, а не выравнивающий столбец пробелов —
+        // иначе первое заглавное слово колонки описания (`PATTERNS are...`) принимается за метавар.
+        r"(?P<value>=[A-Za-z0-9_.:/-]+|\[=[A-Za-z0-9_.:/-]+\]|\s+<[^>]+>| [A-Z][A-Z0-9_]*)?",
+        r"\s+(?P<desc>.+)$"
+    )).unwrap();
 
     let mut flags = Vec::new();
 
-    for cap in re.captures_iter(&text) {
+    for cap in re.captures_iter(text) {
         let short = cap.name("short").map(|m| m.as_str().to_string());
 
         let long = cap.name("long")
@@ -253,30 +883,148 @@ fn fetch_flags(cmd_name: &str, lang: Language) -> Result<Vec<Flag>> {
             if let Some(ref l) = long { if !l.starts_with("--") { continue; } }
             if desc.len() < 2 { continue; }
 
-            flags.push(Flag { short, long, desc, selected: false });
+            let (takes_value, optional_value, metavar) = match cap.name("value") {
+                Some(m) => {
+                    let raw = m.as_str().trim();
+                    let optional_value = raw.starts_with('[');
+                    let metavar = raw
+                        .trim_start_matches('[')
+                        .trim_end_matches(']')
+                        .trim_start_matches('=')
+                        .trim_start_matches('<')
+                        .trim_end_matches('>')
+                        .trim()
+                        .to_string();
+                    (true, optional_value, if metavar.is_empty() { None } else { Some(metavar) })
+                }
+                None => (false, false, None),
+            };
+
+            flags.push(Flag {
+                short,
+                long,
+                desc,
+                selected: false,
+                takes_value,
+                optional_value,
+                metavar,
+                value: None,
+            });
         }
     }
 
-    if flags.is_empty() {
-        return Err(anyhow::anyhow!("Текст справки получен, но флаги не найдены."));
+    flags
+}
+
+// Ищет секцию "Commands:"/"SUBCOMMANDS" в тексте справки и разбирает
+// строки вида `  remote        Manage set of tracked repositories` внутри неё.
+fn parse_subcommands(text: &str) -> Vec<SubCommand> {
+    // Допускает уточняющие слова перед заголовком ("Unit Commands:", "Available Commands:").
+    let heading_re = Regex::new(r"(?mi)^\s*(?:[a-z]+\s+)*(commands|subcommands):?\s*$").unwrap();
+    // Имя может тянуть за собой алиасы через запятую ("build, b    Compile...").
+    let entry_re = Regex::new(r"^\s+([a-z][a-z0-9-]+)(?:,\s*[a-z0-9-]+)*\s{2,}(.+)$").unwrap();
+
+    let mut subcommands = Vec::new();
+    let mut in_section = false;
+
+    for line in text.lines() {
+        if heading_re.is_match(line) {
+            in_section = true;
+            continue;
+        }
+
+        if !in_section {
+            continue;
+        }
+
+        if line.trim().is_empty() {
+            break;
+        }
+
+        // Одна нераспознанная строка не обрывает секцию целиком — пропускаем и идём дальше.
+        if let Some(cap) = entry_re.captures(line) {
+            subcommands.push(SubCommand {
+                name: cap[1].to_string(),
+                desc: cap[2].trim().to_string(),
+            });
+        }
     }
 
-    Ok(flags)
+    subcommands
+}
+
+// Дубликаты между директориями PATH схлопываются через BTreeSet.
+fn scan_path_commands() -> Vec<String> {
+    use std::collections::BTreeSet;
+    use std::os::unix::fs::PermissionsExt;
+
+    let path_var = env::var("PATH").unwrap_or_default();
+    let mut names = BTreeSet::new();
+
+    for dir in env::split_paths(&path_var) {
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(e) => e,
+            Err(_) => continue,
+        };
+
+        for entry in entries.flatten() {
+            // entry.metadata() не следует симлинкам — большинство шимов
+            // (update-alternatives, pyenv/rbenv/asdf, busybox) ставят команды
+            // именно симлинками, так что стат нужно делать по цели ссылки.
+            let metadata = match std::fs::metadata(entry.path()) {
+                Ok(m) => m,
+                Err(_) => continue,
+            };
+            if !metadata.is_file() || metadata.permissions().mode() & 0o111 == 0 {
+                continue;
+            }
+            if let Some(name) = entry.file_name().to_str() {
+                names.insert(name.to_string());
+            }
+        }
+    }
+
+    names.into_iter().collect()
+}
+
+// Получает справку для полного пути команды (напр. ["git", "remote"]) и
+// собирает из неё как флаги, так и дочерние подкоманды.
+fn fetch_node(cmd_path: &[String], lang: Language) -> Result<CommandNode> {
+    let text = fetch_raw_help(cmd_path, lang)?;
+    let flags = parse_flags(&text);
+    let subcommands = parse_subcommands(&text);
+
+    if flags.is_empty() && subcommands.is_empty() {
+        return Err(anyhow::anyhow!("Текст справки получен, но флаги и подкоманды не найдены."));
+    }
+
+    Ok(CommandNode {
+        path: cmd_path.to_vec(),
+        flags,
+        subcommands,
+        raw_text: text,
+    })
 }
 
 fn main() -> Result<()> {
     let args: Vec<String> = env::args().collect();
-    let target = args.get(1).map(|s| s.as_str()).unwrap_or("ls");
-
-    println!("Загрузка справки для '{}'...", target);
 
-    let flags = match fetch_flags(target, Language::System) {
-        Ok(f) => f,
-        Err(e) => {
-            eprintln!("\nОшибка: {}", e);
-            eprintln!("Попробуйте другую команду или проверьте, установлен ли 'man'.");
-            return Ok(());
+    // Без аргумента сразу открываем пикер команд из $PATH, а не падаем
+    // на случайную команду по умолчанию — это тупик для исследования.
+    let mut app = match args.get(1) {
+        Some(target) => {
+            println!("Загрузка справки для '{}'...", target);
+            let root_path = vec![target.to_string()];
+            match fetch_node(&root_path, Language::System) {
+                Ok(n) => App::new(n),
+                Err(e) => {
+                    eprintln!("\nОшибка: {}", e);
+                    eprintln!("Попробуйте другую команду или проверьте, установлен ли 'man'.");
+                    return Ok(());
+                }
+            }
         }
+        None => App::new_picker(),
     };
 
     enable_raw_mode()?;
@@ -285,7 +1033,6 @@ fn main() -> Result<()> {
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    let mut app = App::new(target.to_string(), flags);
     let run_result = run_app(&mut terminal, &mut app);
 
     disable_raw_mode()?;
@@ -299,11 +1046,13 @@ fn main() -> Result<()> {
 
     match app.exit_action {
         ExitAction::Execute => {
+            let path = app.current().path.clone();
             let args = app.get_selected_args();
-            println!(">>> Запуск: {} {}", app.target_cmd, args.join(" "));
+            println!(">>> Запуск: {} {}", path.join(" "), args.join(" "));
             println!("---------------------------------------------------");
 
-            let status = Command::new(&app.target_cmd)
+            let status = Command::new(&path[0])
+                .args(&path[1..])
                 .args(&args)
                 .stdin(Stdio::inherit())
                 .stdout(Stdio::inherit())
@@ -330,34 +1079,175 @@ fn main() -> Result<()> {
     Ok(())
 }
 
-fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> io::Result<()> {
+fn run_app<B: Backend + io::Write>(terminal: &mut Terminal<B>, app: &mut App) -> io::Result<()> {
     loop {
         terminal.draw(|f| ui(f, app))?;
+        draw_hyperlink_overlay(terminal, app)?;
 
         if event::poll(std::time::Duration::from_millis(100))? {
             if let Event::Key(key) = event::read()? {
-                if key.kind == KeyEventKind::Press {
+                if key.kind == KeyEventKind::Press && app.view == View::Picker {
                     match key.code {
                         KeyCode::Char('q') | KeyCode::Esc => {
                             app.exit_action = ExitAction::Cancel;
                             app.should_quit = true;
-                        },
-                        KeyCode::Down | KeyCode::Char('j') => app.next(),
-                        KeyCode::Up | KeyCode::Char('k') => app.previous(),
-                        KeyCode::Char(' ') => app.toggle_selection(),
-                        KeyCode::Enter => {
-                            app.exit_action = ExitAction::Execute;
-                            app.should_quit = true;
                         }
-                        KeyCode::Char('p') => {
-                            app.exit_action = ExitAction::Print;
-                            app.should_quit = true;
+                        KeyCode::Down => app.picker_next(),
+                        KeyCode::Up => app.picker_previous(),
+                        KeyCode::Enter => app.enter_picker_selection(),
+                        KeyCode::Backspace => {
+                            app.picker_query.pop();
+                            app.picker_recompute_filter();
                         }
-                        KeyCode::Char('l') => {
-                            app.toggle_language();
+                        KeyCode::Char(c) => {
+                            app.picker_query.push(c);
+                            app.picker_recompute_filter();
                         }
                         _ => {}
                     }
+                } else if key.kind == KeyEventKind::Press {
+                    match &app.mode {
+                        Mode::Input { flag_idx, buffer } => {
+                            let flag_idx = *flag_idx;
+                            let mut buffer = buffer.clone();
+                            match key.code {
+                                KeyCode::Enter => app.commit_input(flag_idx, buffer),
+                                KeyCode::Esc => app.cancel_input(),
+                                KeyCode::Backspace => {
+                                    buffer.pop();
+                                    app.mode = Mode::Input { flag_idx, buffer };
+                                }
+                                KeyCode::Char(c) => {
+                                    buffer.push(c);
+                                    app.mode = Mode::Input { flag_idx, buffer };
+                                }
+                                _ => {}
+                            }
+                        }
+                        Mode::Normal => match key.code {
+                            KeyCode::Char('q') => {
+                                app.exit_action = ExitAction::Cancel;
+                                app.should_quit = true;
+                            },
+                            KeyCode::Esc => {
+                                if app.filter_active {
+                                    app.reset_filter();
+                                } else {
+                                    app.exit_action = ExitAction::Cancel;
+                                    app.should_quit = true;
+                                }
+                            },
+                            KeyCode::Down | KeyCode::Char('j') => app.next(),
+                            KeyCode::Up | KeyCode::Char('k') => app.previous(),
+                            KeyCode::Char(' ') => {
+                                if let Some(flag_idx) = app.toggle_selection() {
+                                    app.begin_input(flag_idx);
+                                }
+                            }
+                            KeyCode::Right => {
+                                app.enter_subcommand();
+                            }
+                            KeyCode::Backspace | KeyCode::Left => {
+                                app.pop_node();
+                            }
+                            KeyCode::Enter => {
+                                if app.selected_subcommand().is_some() {
+                                    app.enter_subcommand();
+                                } else {
+                                    app.exit_action = ExitAction::Execute;
+                                    app.should_quit = true;
+                                }
+                            }
+                            KeyCode::Char('p') => {
+                                app.exit_action = ExitAction::Print;
+                                app.should_quit = true;
+                            }
+                            KeyCode::Char('l') => {
+                                app.toggle_language();
+                            }
+                            KeyCode::Char('/') => {
+                                app.filter_active = true;
+                                app.mode = Mode::Filter;
+                            }
+                            KeyCode::Char('s') => {
+                                app.save_profile(profile::DEFAULT_PROFILE);
+                            }
+                            KeyCode::Char('S') => {
+                                app.mode = Mode::SaveProfile { buffer: String::new() };
+                            }
+                            KeyCode::Char('L') => {
+                                app.mode = Mode::LoadProfile { buffer: String::new() };
+                            }
+                            KeyCode::Char('d') => {
+                                app.detail_pane = !app.detail_pane;
+                            }
+                            KeyCode::Char('H') => {
+                                app.hyperlinks_enabled = !app.hyperlinks_enabled;
+                            }
+                            KeyCode::Char('P') => {
+                                app.back_to_picker();
+                            }
+                            _ => {}
+                        },
+                        Mode::Filter => match key.code {
+                            KeyCode::Esc => {
+                                app.reset_filter();
+                                app.mode = Mode::Normal;
+                            }
+                            KeyCode::Enter | KeyCode::Down | KeyCode::Up => {
+                                app.mode = Mode::Normal;
+                                if key.code == KeyCode::Down { app.next(); }
+                                if key.code == KeyCode::Up { app.previous(); }
+                            }
+                            KeyCode::Backspace => {
+                                app.filter_query.pop();
+                                app.recompute_filter();
+                            }
+                            KeyCode::Char(c) => {
+                                app.filter_query.push(c);
+                                app.recompute_filter();
+                            }
+                            _ => {}
+                        },
+                        Mode::SaveProfile { buffer } => {
+                            let mut buffer = buffer.clone();
+                            match key.code {
+                                KeyCode::Enter => {
+                                    app.save_profile(&buffer);
+                                    app.mode = Mode::Normal;
+                                }
+                                KeyCode::Esc => app.mode = Mode::Normal,
+                                KeyCode::Backspace => {
+                                    buffer.pop();
+                                    app.mode = Mode::SaveProfile { buffer };
+                                }
+                                KeyCode::Char(c) => {
+                                    buffer.push(c);
+                                    app.mode = Mode::SaveProfile { buffer };
+                                }
+                                _ => {}
+                            }
+                        }
+                        Mode::LoadProfile { buffer } => {
+                            let mut buffer = buffer.clone();
+                            match key.code {
+                                KeyCode::Enter => {
+                                    app.load_profile(&buffer);
+                                    app.mode = Mode::Normal;
+                                }
+                                KeyCode::Esc => app.mode = Mode::Normal,
+                                KeyCode::Backspace => {
+                                    buffer.pop();
+                                    app.mode = Mode::LoadProfile { buffer };
+                                }
+                                KeyCode::Char(c) => {
+                                    buffer.push(c);
+                                    app.mode = Mode::LoadProfile { buffer };
+                                }
+                                _ => {}
+                            }
+                        }
+                    }
                 }
             }
         }
@@ -368,27 +1258,172 @@ fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> io::Result<
     }
 }
 
+// ratatui не умеет нести произвольные escape-последовательности в `Span`,
+// поэтому имя команды в предпросмотре и имена видимых флагов в списке
+// дорисовываются напрямую через backend после `terminal.draw`, той же
+// разбивкой на chunks, что и в `ui`.
+fn draw_hyperlink_overlay<B: Backend + io::Write>(terminal: &mut Terminal<B>, app: &App) -> io::Result<()> {
+    if !app.hyperlinks_enabled || app.view == View::Picker {
+        return Ok(());
+    }
+
+    let size = terminal.size()?;
+    let area = Rect::new(0, 0, size.width, size.height);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(5)])
+        .split(area);
+    let preview_area = chunks[1];
+
+    let label = "Предпросмотр: ";
+    let text_row = preview_area.y + 1;
+    let cmd_col = preview_area.x + 1 + label.chars().count() as u16;
+
+    let cmd_name = app.current().path[0].clone();
+    let url = doc_url(&app.current().path);
+
+    execute!(terminal.backend_mut(), MoveTo(cmd_col, text_row))?;
+    write!(terminal.backend_mut(), "{}", osc8_hyperlink(&cmd_name, &url))?;
+
+    let main_area = if app.detail_pane {
+        Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(chunks[0])[0]
+    } else {
+        chunks[0]
+    };
+    let list_area = Rect {
+        x: main_area.x + 1,
+        y: main_area.y + 1,
+        width: main_area.width.saturating_sub(2),
+        height: main_area.height.saturating_sub(2),
+    };
+
+    let node = app.current();
+    let visible_flags: Vec<&Flag> = if app.filter_active {
+        app.filtered_indices.iter().filter_map(|&i| node.flags.get(i)).collect()
+    } else {
+        node.flags.iter().collect()
+    };
+    // Ширина, которую List резервирует под `highlight_symbol`, когда что-то выбрано.
+    let reserved = if app.list_state.selected().is_some() { 2u16 } else { 0u16 };
+    let offset = app.list_state.offset();
+
+    for (row, flag) in visible_flags.iter().enumerate().skip(offset).take(list_area.height as usize) {
+        let raw_label = flag.flags_label();
+        let label = raw_label.trim();
+        if label.is_empty() {
+            continue;
+        }
+        let lead = raw_label.chars().take_while(|c| *c == ' ').count() as u16;
+        let x = list_area.x + reserved + 4 + lead;
+        if x >= list_area.x + list_area.width {
+            continue;
+        }
+        let y = list_area.y + (row - offset) as u16;
+
+        execute!(terminal.backend_mut(), MoveTo(x, y))?;
+        write!(terminal.backend_mut(), "{}", osc8_hyperlink(label, &url))?;
+    }
+
+    io::Write::flush(terminal.backend_mut())?;
+
+    Ok(())
+}
+
 fn ui(f: &mut Frame, app: &mut App) {
+    if app.view == View::Picker {
+        ui_picker(f, app);
+        return;
+    }
+
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([Constraint::Min(0), Constraint::Length(5)]).split(f.area());
 
-    let items: Vec<ListItem> = app.flags.iter().map(|flag| {
+    let node = app.current();
+    let path_str = node.command_string();
+
+    let visible_flags: Box<dyn Iterator<Item = &Flag>> = if app.filter_active {
+        Box::new(app.filtered_indices.iter().filter_map(|&i| node.flags.get(i)))
+    } else {
+        Box::new(node.flags.iter())
+    };
+
+    let mut items: Vec<ListItem> = visible_flags.map(|flag| {
         let style = if flag.selected { Style::default().fg(Color::Green) } else { Style::default() };
         ListItem::new(flag.to_display_string()).style(style)
     }).collect();
 
+    items.extend(node.subcommands.iter().map(|sub| {
+        ListItem::new(sub.to_display_string()).style(Style::default().fg(Color::Cyan))
+    }));
+
     let source = match app.current_lang {
         Language::System => "Sys",
         Language::English => "EN",
     };
 
+    let profile_suffix = if app.active_profile.is_empty() {
+        String::new()
+    } else {
+        format!(" (профиль: {})", app.active_profile)
+    };
+
+    let title = if app.filter_active {
+        format!(" rlhelp: {} [{}]{} /{}_ ", path_str, source, profile_suffix, app.filter_query)
+    } else {
+        format!(" rlhelp: {} [{}]{} ", path_str, source, profile_suffix)
+    };
+
     let list = List::new(items)
-        .block(Block::default().borders(Borders::ALL).title(format!(" rlhelp: {} [{}] ", app.target_cmd, source)))
+        .block(Block::default().borders(Borders::ALL).title(title))
         .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
         .highlight_symbol("> ");
 
-    f.render_stateful_widget(list, chunks[0], &mut app.list_state);
+    // Собираем всё, что панели деталей нужно от `app`, пока список ещё не
+    // забрал единственный мутабельный заём `list_state`.
+    let detail_pane_data = if app.detail_pane {
+        let raw_text = app.current().raw_text.clone();
+        let focus_tokens: Vec<String> = app.focused_flag()
+            .map(|f| [f.short.clone(), f.long.clone()].into_iter().flatten().collect())
+            .unwrap_or_default();
+        Some((raw_text, focus_tokens))
+    } else {
+        None
+    };
+
+    let main_area = if app.detail_pane {
+        let halves = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(chunks[0]);
+        f.render_stateful_widget(list, halves[0], &mut app.list_state);
+        Some(halves[1])
+    } else {
+        f.render_stateful_widget(list, chunks[0], &mut app.list_state);
+        None
+    };
+
+    if let (Some(detail_area), Some((raw_text, focus_tokens))) = (main_area, detail_pane_data) {
+        let focus_refs: Vec<&str> = focus_tokens.iter().map(|s| s.as_str()).collect();
+
+        let lines: Vec<Line> = raw_text.lines()
+            .map(|line| highlight_help_line(line, &focus_refs))
+            .collect();
+
+        let scroll = focus_refs.iter()
+            .find_map(|tok| raw_text.lines().position(|line| line_has_token(line, tok)))
+            .map(|pos| pos.saturating_sub(3) as u16)
+            .unwrap_or(0);
+
+        let detail = Paragraph::new(lines)
+            .block(Block::default().borders(Borders::ALL).title(" Справка (man/--help) "))
+            .scroll((scroll, 0));
+
+        f.render_widget(detail, detail_area);
+    }
 
     let help_text = vec![
         Line::from(vec![
@@ -397,12 +1432,24 @@ fn ui(f: &mut Frame, app: &mut App) {
         ]),
         Line::from(""),
         Line::from(vec![
-            Span::styled("[Enter]", Style::default().fg(Color::Cyan)),
-            Span::raw(": Старт  "),
+            Span::styled("[Enter/→]", Style::default().fg(Color::Cyan)),
+            Span::raw(": Старт/Зайти  "),
+            Span::styled("[←/Backspace]", Style::default().fg(Color::Cyan)),
+            Span::raw(": Назад  "),
             Span::styled("[p]", Style::default().fg(Color::Cyan)),
             Span::raw(": Печать  "),
             Span::styled("[l]", Style::default().fg(Color::Magenta)),
             Span::raw(": Язык  "),
+            Span::styled("[/]", Style::default().fg(Color::Magenta)),
+            Span::raw(": Фильтр  "),
+            Span::styled("[s/S/L]", Style::default().fg(Color::Magenta)),
+            Span::raw(": Профиль  "),
+            Span::styled("[d]", Style::default().fg(Color::Magenta)),
+            Span::raw(": Справка  "),
+            Span::styled("[H]", Style::default().fg(Color::Magenta)),
+            Span::raw(": Ссылки  "),
+            Span::styled("[P]", Style::default().fg(Color::Magenta)),
+            Span::raw(": Пикер  "),
             Span::styled("[Space]", Style::default().fg(Color::DarkGray)),
             Span::raw(": Выбор  "),
             Span::styled("[Esc]", Style::default().fg(Color::Red)),
@@ -415,4 +1462,241 @@ fn ui(f: &mut Frame, app: &mut App) {
         .wrap(ratatui::widgets::Wrap { trim: true });
 
     f.render_widget(preview, chunks[1]);
-}
\ No newline at end of file
+
+    if let Mode::Input { flag_idx, buffer } = &app.mode {
+        let label = app.current().flags.get(*flag_idx)
+            .map(|f| f.long.clone().or_else(|| f.short.clone()).unwrap_or_default())
+            .unwrap_or_default();
+
+        let area = centered_rect(50, 15, f.area());
+        let input = Paragraph::new(buffer.as_str())
+            .block(Block::default().borders(Borders::ALL).title(format!(" Значение для {} ", label)));
+
+        f.render_widget(Clear, area);
+        f.render_widget(input, area);
+    }
+
+    if let Mode::SaveProfile { buffer } = &app.mode {
+        let area = centered_rect(50, 15, f.area());
+        let input = Paragraph::new(buffer.as_str())
+            .block(Block::default().borders(Borders::ALL).title(" Сохранить профиль как "));
+        f.render_widget(Clear, area);
+        f.render_widget(input, area);
+    }
+
+    if let Mode::LoadProfile { buffer } = &app.mode {
+        let area = centered_rect(50, 15, f.area());
+        let input = Paragraph::new(buffer.as_str())
+            .block(Block::default().borders(Borders::ALL).title(" Загрузить профиль "));
+        f.render_widget(Clear, area);
+        f.render_widget(input, area);
+    }
+}
+
+fn ui_picker(f: &mut Frame, app: &mut App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(5)])
+        .split(f.area());
+
+    let items: Vec<ListItem> = app.picker_filtered.iter()
+        .filter_map(|&i| app.picker_commands.get(i))
+        .map(|name| ListItem::new(name.clone()))
+        .collect();
+
+    let title = format!(
+        " rlhelp: выбор команды ({} из {}) /{}_ ",
+        app.picker_filtered.len(),
+        app.picker_commands.len(),
+        app.picker_query
+    );
+
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("> ");
+
+    f.render_stateful_widget(list, chunks[0], &mut app.picker_state);
+
+    let mut help_text = vec![
+        Line::from(vec![
+            Span::styled("Наберите имя команды для поиска. ", Style::default().fg(Color::Yellow)),
+        ]),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("[Enter]", Style::default().fg(Color::Cyan)),
+            Span::raw(": Выбрать  "),
+            Span::styled("[↑/↓]", Style::default().fg(Color::Cyan)),
+            Span::raw(": Навигация  "),
+            Span::styled("[Esc/q]", Style::default().fg(Color::Red)),
+            Span::raw(": Выход"),
+        ]),
+    ];
+
+    if let Some(err) = &app.picker_error {
+        help_text.insert(0, Line::from(Span::styled(
+            format!("Ошибка: {}", err),
+            Style::default().fg(Color::Red),
+        )));
+    }
+
+    let preview = Paragraph::new(help_text)
+        .block(Block::default().borders(Borders::ALL))
+        .wrap(ratatui::widgets::Wrap { trim: true });
+
+    f.render_widget(preview, chunks[1]);
+}
+
+// Прямоугольник по центру экрана заданного процента ширины/высоты — под попап ввода.
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_flags_value_forms() {
+        let text = "\n  -o, --output=FILE        write output to FILE\n  --jobs[=N]               run N jobs in parallel\n  -v, --verbose            be verbose\n";
+        let flags = parse_flags(text);
+
+        let output = flags.iter().find(|f| f.long.as_deref() == Some("--output")).unwrap();
+        assert!(output.takes_value);
+        assert!(!output.optional_value);
+        assert_eq!(output.metavar.as_deref(), Some("FILE"));
+
+        let jobs = flags.iter().find(|f| f.long.as_deref() == Some("--jobs")).unwrap();
+        assert!(jobs.takes_value);
+        assert!(jobs.optional_value);
+        assert_eq!(jobs.metavar.as_deref(), Some("N"));
+
+        let verbose = flags.iter().find(|f| f.long.as_deref() == Some("--verbose")).unwrap();
+        assert!(!verbose.takes_value);
+        assert_eq!(verbose.metavar, None);
+    }
+
+    #[test]
+    fn parse_flags_does_not_treat_aligned_description_word_as_metavar() {
+        let text = "\n  -E, --extended-regexp     PATTERNS are extended regular expressions\n";
+        let flags = parse_flags(text);
+
+        let extended = flags.iter().find(|f| f.long.as_deref() == Some("--extended-regexp")).unwrap();
+        assert!(!extended.takes_value);
+        assert_eq!(extended.metavar, None);
+        assert_eq!(extended.desc, "PATTERNS are extended regular expressions");
+    }
+
+    #[test]
+    fn fuzzy_score_subsequence_and_bonuses() {
+        assert_eq!(fuzzy_score("", "--output"), Some(0));
+        assert_eq!(fuzzy_score("xyz", "--output"), None);
+
+        let prefix = fuzzy_score("out", "--output").unwrap();
+        let scattered = fuzzy_score("otu", "--output").unwrap();
+        assert!(prefix > scattered);
+
+        let consecutive = fuzzy_score("ou", "--output").unwrap();
+        let gapped = fuzzy_score("ot", "--output").unwrap();
+        assert!(consecutive > gapped);
+    }
+
+    #[test]
+    fn parse_subcommands_stops_at_blank_line() {
+        let text = "\
+usage: git [...]
+
+Commands:
+  remote        Manage set of tracked repositories
+  log           Show commit logs
+
+Some other section:
+  unrelated     not a subcommand
+";
+        let subcommands = parse_subcommands(text);
+        assert_eq!(subcommands.len(), 2);
+        assert_eq!(subcommands[0].name, "remote");
+        assert_eq!(subcommands[0].desc, "Manage set of tracked repositories");
+        assert_eq!(subcommands[1].name, "log");
+    }
+
+    #[test]
+    fn parse_subcommands_no_heading_returns_empty() {
+        assert!(parse_subcommands("usage: git [...]\n\n  remote   Manage repos\n").is_empty());
+    }
+
+    #[test]
+    fn parse_subcommands_heading_with_leading_qualifier() {
+        let text = "\
+systemctl [OPTIONS...] COMMAND ...
+
+Unit Commands:
+  list-units        List units.
+  start             Start units.
+";
+        let subcommands = parse_subcommands(text);
+        assert_eq!(subcommands.len(), 2);
+        assert_eq!(subcommands[0].name, "list-units");
+        assert_eq!(subcommands[1].name, "start");
+    }
+
+    #[test]
+    fn parse_subcommands_strips_comma_separated_aliases() {
+        let text = "\
+Commands:
+  build, b          Compile the current package
+  check, c          Check a local package
+";
+        let subcommands = parse_subcommands(text);
+        assert_eq!(subcommands.len(), 2);
+        assert_eq!(subcommands[0].name, "build");
+        assert_eq!(subcommands[0].desc, "Compile the current package");
+        assert_eq!(subcommands[1].name, "check");
+    }
+
+    fn flag(short: Option<&str>, long: Option<&str>, takes_value: bool, optional_value: bool) -> Flag {
+        Flag {
+            short: short.map(String::from),
+            long: long.map(String::from),
+            desc: "desc".to_string(),
+            selected: false,
+            takes_value,
+            optional_value,
+            metavar: None,
+            value: None,
+        }
+    }
+
+    #[test]
+    fn apply_profile_args_matches_by_match_key() {
+        let mut flags = vec![
+            flag(Some("-o"), Some("--output"), true, false),
+            flag(None, Some("--jobs"), true, true),
+            flag(Some("-v"), Some("--verbose"), false, false),
+        ];
+
+        apply_profile_args(&mut flags, &["--output=FILE".to_string(), "--verbose".to_string()]);
+
+        assert!(flags[0].selected);
+        assert_eq!(flags[0].value.as_deref(), Some("FILE"));
+        assert!(!flags[1].selected);
+        assert!(flags[2].selected);
+        assert_eq!(flags[2].value, None);
+    }
+}
@@ -0,0 +1,61 @@
+// Хранится в JSON под `$XDG_CONFIG_HOME/rlhelp/profiles.json`
+// (или `~/.config/rlhelp/profiles.json`).
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+// Профиль, подхватываемый автоматически без явной загрузки клавишей `L`.
+pub const DEFAULT_PROFILE: &str = "default";
+
+#[derive(Default, Serialize, Deserialize)]
+pub struct ProfileStore {
+    // команда (полный путь, напр. "git remote") -> имя профиля -> сохранённые аргументы
+    commands: HashMap<String, HashMap<String, Vec<String>>>,
+}
+
+impl ProfileStore {
+    pub fn load() -> Self {
+        match Self::path().and_then(|p| fs::read_to_string(p).map_err(Into::into)) {
+            Ok(text) => serde_json::from_str(&text).unwrap_or_default(),
+            Err(_) => Self::default(),
+        }
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path()?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn get(&self, command: &str, profile: &str) -> Option<&Vec<String>> {
+        self.commands.get(command)?.get(profile)
+    }
+
+    pub fn set(&mut self, command: &str, profile: &str, args: Vec<String>) {
+        self.commands
+            .entry(command.to_string())
+            .or_default()
+            .insert(profile.to_string(), args);
+    }
+
+    fn path() -> Result<PathBuf> {
+        let config_dir = match std::env::var("XDG_CONFIG_HOME") {
+            Ok(dir) if !dir.is_empty() => PathBuf::from(dir),
+            _ => PathBuf::from(std::env::var("HOME")?).join(".config"),
+        };
+        Ok(config_dir.join("rlhelp").join("profiles.json"))
+    }
+}
+
+pub fn parse_arg(raw: &str) -> (String, Option<String>) {
+    match raw.split_once('=') {
+        Some((key, value)) => (key.to_string(), Some(value.to_string())),
+        None => (raw.to_string(), None),
+    }
+}